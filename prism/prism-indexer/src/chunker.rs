@@ -7,44 +7,398 @@ pub const DEFAULT_CHUNK_SIZE: usize = 512;
 pub const DEFAULT_OVERLAP: usize = 128;
 pub const MAX_CHUNK_SIZE: usize = 1000;
 
-/// Chunk code into semantic units
-pub fn chunk_code(root: &Node, source: &str, language: &str) -> Vec<CodeChunk> {
-    let mut chunks = Vec::new();
-
-    // Extract functions and classes first
-    let functions = crate::extractor::extract_functions(root, source);
-    let classes = crate::extractor::extract_classes(root, source);
-
-    // For now, create a single chunk for the entire file
-    // TODO: Implement proper semantic chunking
-    let text = source.to_string();
-    let token_count = estimate_tokens(&text);
-
-    chunks.push(CodeChunk {
-        id: Uuid::new_v4().to_string(),
-        text,
-        start_line: 1,
-        end_line: source.lines().count(),
-        tokens: token_count,
-        language: language.to_string(),
-        functions: functions.clone(),
-        classes: classes.clone(),
-        imports: Vec::new(),
-        dependencies: Vec::new(),
-    });
+/// Strategy used to approximate how many model tokens a span of text costs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenEstimator {
+    /// Fast heuristic: ~4 characters per token
+    Heuristic,
+    /// Approximates subword tokenization by splitting on identifier/
+    /// punctuation/numeric/whitespace boundaries, further breaking long
+    /// camelCase or snake_case identifiers into their component words
+    Accurate,
+}
+
+impl Default for TokenEstimator {
+    fn default() -> Self {
+        TokenEstimator::Heuristic
+    }
+}
+
+/// Chunk code into semantic units.
+///
+/// Greedily packs whole top-level declarations (functions, classes, import
+/// blocks, ...) into a chunk until the running token estimate would exceed
+/// `DEFAULT_CHUNK_SIZE`, then starts a new chunk at the next declaration
+/// boundary. A single declaration that alone exceeds `MAX_CHUNK_SIZE` is
+/// handed to `split_large_chunk`, which subdivides it at its own child
+/// boundaries so no chunk ever splits mid-statement.
+pub fn chunk_code(
+    root: &Node,
+    source: &str,
+    language: &str,
+    estimator: TokenEstimator,
+) -> Vec<CodeChunk> {
+    let functions = crate::extractor::extract_functions(root, source, language);
+    let classes = crate::extractor::extract_classes(root, source, language);
+    let imports = crate::extractor::extract_imports(root, source);
+
+    let mut cursor = root.walk();
+    let ranges = pack_children(root.children(&mut cursor), source, estimator);
+
+    build_chunks(ranges, source, language, estimator, &functions, &classes, &imports)
+}
+
+/// Greedily group a sequence of sibling nodes into `(start_line, end_line)`
+/// ranges (1-indexed, inclusive), recursing into `split_large_chunk` for any
+/// single node that alone exceeds `MAX_CHUNK_SIZE`.
+fn pack_children<'a>(
+    nodes: impl Iterator<Item = Node<'a>>,
+    source: &str,
+    estimator: TokenEstimator,
+) -> Vec<(usize, usize)> {
+    let mut ranges = Vec::new();
+    let mut group_start: Option<usize> = None;
+    let mut group_end = 0usize;
+    let mut group_tokens = 0usize;
+
+    for node in nodes {
+        let tokens = estimate_tokens(&source[node.byte_range()], estimator);
+        let start_line = node.start_position().row + 1;
+        let end_line = node.end_position().row + 1;
+
+        if tokens > MAX_CHUNK_SIZE {
+            if let Some(s) = group_start.take() {
+                ranges.push((s, group_end));
+                group_tokens = 0;
+            }
+            ranges.extend(split_large_chunk(&node, source, estimator));
+            continue;
+        }
+
+        if group_tokens + tokens > DEFAULT_CHUNK_SIZE && group_start.is_some() {
+            let s = group_start.take().unwrap();
+            ranges.push((s, group_end));
+            group_tokens = 0;
+        }
+
+        if group_start.is_none() {
+            group_start = Some(start_line);
+        }
+        group_end = end_line;
+        group_tokens += tokens;
+    }
+
+    if let Some(s) = group_start {
+        ranges.push((s, group_end));
+    }
+
+    ranges
+}
+
+/// Estimate token count from text using the selected strategy
+fn estimate_tokens(text: &str, estimator: TokenEstimator) -> usize {
+    match estimator {
+        TokenEstimator::Heuristic => text.len() / 4,
+        TokenEstimator::Accurate => estimate_tokens_accurate(text),
+    }
+}
+
+/// Count tokens by splitting on identifier/punctuation/numeric/whitespace
+/// boundaries, further breaking long identifiers into their component
+/// camelCase/snake_case words the way subword tokenizers fragment them.
+fn estimate_tokens_accurate(text: &str) -> usize {
+    let mut count = 0;
+    let mut chars = text.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+
+        if c.is_ascii_digit() {
+            while chars.peek().map(|c| c.is_ascii_digit() || *c == '.').unwrap_or(false) {
+                chars.next();
+            }
+            count += 1;
+            continue;
+        }
+
+        if c.is_alphabetic() || c == '_' {
+            let mut word = String::new();
+            while chars.peek().map(|c| c.is_alphanumeric() || *c == '_').unwrap_or(false) {
+                word.push(chars.next().unwrap());
+            }
+            count += identifier_word_count(&word);
+            continue;
+        }
+
+        // A run of punctuation (operators, brackets, ...) counts as one token.
+        chars.next();
+        while chars
+            .peek()
+            .map(|c| !c.is_whitespace() && !c.is_alphanumeric() && *c != '_')
+            .unwrap_or(false)
+        {
+            chars.next();
+        }
+        count += 1;
+    }
+
+    count
+}
+
+/// Split a long identifier into its component camelCase/snake_case words;
+/// short identifiers are left as a single token.
+fn identifier_word_count(identifier: &str) -> usize {
+    if identifier.len() <= 8 {
+        return 1;
+    }
+
+    let mut words = 0;
+    for segment in identifier.split('_').filter(|s| !s.is_empty()) {
+        let mut boundaries = 1;
+        let mut prev_lower = false;
+        for c in segment.chars() {
+            if c.is_uppercase() && prev_lower {
+                boundaries += 1;
+            }
+            prev_lower = c.is_lowercase();
+        }
+        words += boundaries;
+    }
+
+    words.max(1)
+}
+
+/// Recursively subdivide a node that alone exceeds `MAX_CHUNK_SIZE`, packing
+/// its children (e.g. a class's methods) the same way `chunk_code` packs
+/// top-level declarations. A node with no children (e.g. a single oversized
+/// string literal) has no statement boundary to split on, so it is kept
+/// whole.
+pub fn split_large_chunk(
+    node: &Node,
+    source: &str,
+    estimator: TokenEstimator,
+) -> Vec<(usize, usize)> {
+    let mut cursor = node.walk();
+    let children: Vec<Node> = node.children(&mut cursor).collect();
+
+    if children.is_empty() {
+        return vec![(node.start_position().row + 1, node.end_position().row + 1)];
+    }
+
+    pack_children(children.into_iter(), source, estimator)
+}
+
+/// Turn packed line ranges into `CodeChunk`s, prepending `DEFAULT_OVERLAP`
+/// tokens' worth of the previous chunk's trailing lines to each chunk after
+/// the first, and keeping only the functions/classes/imports whose line
+/// range falls inside the chunk.
+fn build_chunks(
+    ranges: Vec<(usize, usize)>,
+    source: &str,
+    language: &str,
+    estimator: TokenEstimator,
+    functions: &[FunctionInfo],
+    classes: &[ClassInfo],
+    imports: &[ImportInfo],
+) -> Vec<CodeChunk> {
+    let lines: Vec<&str> = source.lines().collect();
+    if ranges.is_empty() {
+        return vec![CodeChunk {
+            id: Uuid::new_v4().to_string(),
+            symbols: chunk_symbols(functions, classes),
+            content_hash: content_hash(source),
+            text: source.to_string(),
+            start_line: 1,
+            end_line: lines.len().max(1),
+            tokens: estimate_tokens(source, estimator),
+            language: language.to_string(),
+            functions: functions.to_vec(),
+            classes: classes.to_vec(),
+            imports: imports.to_vec(),
+            dependencies: imports.iter().map(|i| i.source.clone()).collect(),
+        }];
+    }
+
+    let mut chunks = Vec::with_capacity(ranges.len());
+    let mut prev_body: Option<String> = None;
+
+    for (start_line, end_line) in ranges {
+        let end = end_line.min(lines.len());
+        let body = lines[start_line - 1..end].join("\n");
+
+        let overlap = prev_body
+            .as_ref()
+            .map(|prev| trailing_overlap(prev, DEFAULT_OVERLAP, estimator))
+            .unwrap_or_default();
+        let text = if overlap.is_empty() {
+            body.clone()
+        } else {
+            format!("{}\n{}", overlap, body)
+        };
+
+        let chunk_functions: Vec<FunctionInfo> = functions
+            .iter()
+            .filter(|f| f.start_line >= start_line && f.end_line <= end_line)
+            .cloned()
+            .collect();
+        let chunk_classes: Vec<ClassInfo> = classes
+            .iter()
+            .filter(|c| c.start_line >= start_line && c.end_line <= end_line)
+            .cloned()
+            .collect();
+        let chunk_imports: Vec<ImportInfo> = imports
+            .iter()
+            .filter(|i| {
+                let row = i.location.start_row + 1;
+                row >= start_line && row <= end_line
+            })
+            .cloned()
+            .collect();
+        let dependencies = chunk_imports.iter().map(|i| i.source.clone()).collect();
+
+        chunks.push(CodeChunk {
+            id: Uuid::new_v4().to_string(),
+            symbols: chunk_symbols(&chunk_functions, &chunk_classes),
+            content_hash: content_hash(&text),
+            tokens: estimate_tokens(&text, estimator),
+            text,
+            start_line,
+            end_line,
+            language: language.to_string(),
+            functions: chunk_functions,
+            classes: chunk_classes,
+            imports: chunk_imports,
+            dependencies,
+        });
+
+        prev_body = Some(body);
+    }
 
     chunks
 }
 
-/// Estimate token count from text
-fn estimate_tokens(text: &str) -> usize {
-    // Rough estimation: ~4 characters per token
-    text.len() / 4
+/// Flatten a chunk's contained function/class names into a single symbol list
+fn chunk_symbols(functions: &[FunctionInfo], classes: &[ClassInfo]) -> Vec<String> {
+    functions
+        .iter()
+        .map(|f| f.name.clone())
+        .chain(classes.iter().map(|c| c.name.clone()))
+        .collect()
 }
 
-/// Split large chunks into smaller pieces
-pub fn split_large_chunk(chunk: &CodeChunk, target_size: usize) -> Vec<CodeChunk> {
-    // TODO: Implement AST-aware chunk splitting
-    // For now, return the original chunk
-    vec![chunk.clone()]
+/// Hash a chunk's text (normalized to ignore trailing whitespace) so an
+/// embedding cache can tell whether a chunk's content actually changed.
+fn content_hash(text: &str) -> String {
+    let normalized: String = text
+        .lines()
+        .map(|line| line.trim_end())
+        .collect::<Vec<_>>()
+        .join("\n");
+    blake3::hash(normalized.as_bytes()).to_hex().to_string()
+}
+
+/// Return the longest run of trailing lines of `text` whose combined token
+/// estimate does not exceed `overlap_tokens`. The last line is always
+/// included, even when it alone exceeds `overlap_tokens`.
+fn trailing_overlap(text: &str, overlap_tokens: usize, estimator: TokenEstimator) -> String {
+    let lines: Vec<&str> = text.lines().collect();
+    let mut taken: Vec<&str> = Vec::new();
+    let mut tokens = 0usize;
+
+    for line in lines.iter().rev() {
+        let line_tokens = estimate_tokens(line, estimator);
+        if tokens + line_tokens > overlap_tokens && !taken.is_empty() {
+            break;
+        }
+        taken.push(line);
+        tokens += line_tokens;
+    }
+
+    taken.reverse();
+    taken.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tree_sitter::Parser;
+
+    fn parse_rust(source: &str) -> tree_sitter::Tree {
+        let mut parser = Parser::new();
+        parser.set_language(tree_sitter_rust::language()).unwrap();
+        parser.parse(source, None).unwrap()
+    }
+
+    #[test]
+    fn chunk_code_keeps_small_file_in_one_chunk() {
+        let source = "fn a() {}\nfn b() {}\nfn c() {}\n";
+        let tree = parse_rust(source);
+        let chunks = chunk_code(&tree.root_node(), source, "rust", TokenEstimator::Heuristic);
+
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].start_line, 1);
+        assert_eq!(chunks[0].symbols, vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn chunk_code_splits_at_default_size_and_overlaps_chunks() {
+        // Each function is well under DEFAULT_CHUNK_SIZE alone, but enough of
+        // them together should force a second chunk, which must start with
+        // trailing lines from the first chunk's body.
+        let mut source = String::new();
+        for i in 0..80 {
+            source.push_str(&format!("fn func_{i}() {{\n    let _ = {i};\n}}\n"));
+        }
+        let tree = parse_rust(&source);
+        let chunks = chunk_code(&tree.root_node(), &source, "rust", TokenEstimator::Heuristic);
+
+        assert!(chunks.len() > 1, "expected the greedy packer to split into multiple chunks");
+        for chunk in &chunks[1..] {
+            let own_lines = chunk.end_line - chunk.start_line + 1;
+            assert!(
+                chunk.text.lines().count() > own_lines,
+                "expected chunk text to include overlap lines from the previous chunk"
+            );
+        }
+    }
+
+    #[test]
+    fn split_large_chunk_subdivides_at_child_boundaries() {
+        // A single function whose body alone exceeds MAX_CHUNK_SIZE tokens
+        // must be split at its own statement boundaries rather than kept
+        // whole or split mid-statement.
+        let mut body = String::new();
+        for i in 0..500 {
+            body.push_str(&format!("    let _ = {i};\n"));
+        }
+        let source = format!("fn big() {{\n{body}}}\n");
+        let tree = parse_rust(&source);
+        let func_node = tree.root_node().child(0).unwrap();
+
+        let ranges = split_large_chunk(&func_node, &source, TokenEstimator::Heuristic);
+
+        assert!(ranges.len() > 1, "an oversized function should be split into several ranges");
+        for (start, end) in &ranges {
+            assert!(start <= end);
+        }
+    }
+
+    #[test]
+    fn identifier_word_count_splits_long_identifiers_on_case_and_underscore() {
+        assert_eq!(identifier_word_count("short"), 1);
+        assert_eq!(identifier_word_count("camelCaseLongName"), 4);
+        assert_eq!(identifier_word_count("snake_case_identifier"), 3);
+    }
+
+    #[test]
+    fn estimate_tokens_accurate_counts_words_numbers_and_punctuation() {
+        assert_eq!(estimate_tokens_accurate("let x = 1;"), 5);
+        assert_eq!(
+            estimate_tokens_accurate("fn camelCaseLongName() {}"),
+            7
+        );
+    }
 }
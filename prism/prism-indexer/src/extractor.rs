@@ -1,22 +1,25 @@
-use crate::types::{FunctionInfo, ClassInfo, ImportInfo, ErrorNode, SourceLocation};
+use crate::types::{FunctionInfo, ClassInfo, ImportInfo, ErrorNode, Severity, SourceLocation};
 use tree_sitter::Node;
 
-/// Extract all functions from the AST
-pub fn extract_functions(root: &Node, source: &str) -> Vec<FunctionInfo> {
+/// Extract all functions from the AST. Covers JS/TS/Go `function_declaration`,
+/// Python `function_definition`, JS/TS/Java `method_definition`/
+/// `method_declaration`, and Rust `function_item`.
+pub fn extract_functions(root: &Node, source: &str, language: &str) -> Vec<FunctionInfo> {
     let mut functions = Vec::new();
 
     // Walk the tree and find function definitions
     let mut cursor = root.walk();
     for child in root.children(&mut cursor) {
         match child.kind() {
-            "function_declaration" | "function_definition" | "method_definition" => {
-                if let Some(func) = extract_function_info(&child, source) {
+            "function_declaration" | "function_definition" | "method_definition"
+            | "function_item" | "method_declaration" => {
+                if let Some(func) = extract_function_info(&child, source, language) {
                     functions.push(func);
                 }
             }
             _ => {
                 // Recurse into child nodes
-                functions.extend(extract_functions(&child, source));
+                functions.extend(extract_functions(&child, source, language));
             }
         }
     }
@@ -25,17 +28,17 @@ pub fn extract_functions(root: &Node, source: &str) -> Vec<FunctionInfo> {
 }
 
 /// Extract all classes from the AST
-pub fn extract_classes(root: &Node, source: &str) -> Vec<ClassInfo> {
+pub fn extract_classes(root: &Node, source: &str, language: &str) -> Vec<ClassInfo> {
     let mut classes = Vec::new();
 
     let mut cursor = root.walk();
     for child in root.children(&mut cursor) {
         if child.kind() == "class_declaration" || child.kind() == "class_definition" {
-            if let Some(class) = extract_class_info(&child, source) {
+            if let Some(class) = extract_class_info(&child, source, language) {
                 classes.push(class);
             }
         } else {
-            classes.extend(extract_classes(&child, source));
+            classes.extend(extract_classes(&child, source, language));
         }
     }
 
@@ -43,7 +46,7 @@ pub fn extract_classes(root: &Node, source: &str) -> Vec<ClassInfo> {
 }
 
 /// Extract information from a function node
-fn extract_function_info(node: &Node, source: &str) -> Option<FunctionInfo> {
+fn extract_function_info(node: &Node, source: &str, language: &str) -> Option<FunctionInfo> {
     let name_node = node.child_by_field_name("name")?;
     let name = name_node.utf8_text(source.as_bytes()).ok()?.to_string();
 
@@ -56,19 +59,19 @@ fn extract_function_info(node: &Node, source: &str) -> Option<FunctionInfo> {
         .to_string();
 
     Some(FunctionInfo {
-        name,
+        name: name.clone(),
         signature,
         start_line,
         end_line,
-        parameters: Vec::new(),
-        return_type: None,
+        parameters: extract_parameters(node, source),
+        return_type: extract_return_type(node, source),
         is_async: node.child_by_field_name("async").is_some(),
-        is_exported: false,
+        is_exported: is_exported(node, source, &name, language),
     })
 }
 
 /// Extract information from a class node
-fn extract_class_info(node: &Node, source: &str) -> Option<ClassInfo> {
+fn extract_class_info(node: &Node, source: &str, language: &str) -> Option<ClassInfo> {
     let name_node = node.child_by_field_name("name")?;
     let name = name_node.utf8_text(source.as_bytes()).ok()?.to_string();
 
@@ -81,8 +84,8 @@ fn extract_class_info(node: &Node, source: &str) -> Option<ClassInfo> {
 
     let mut cursor = body_node.walk();
     for child in body_node.children(&mut cursor) {
-        if child.kind() == "method_definition" {
-            if let Some(method) = extract_function_info(&child, source) {
+        if child.kind() == "method_definition" || child.kind() == "method_declaration" {
+            if let Some(method) = extract_function_info(&child, source, language) {
                 methods.push(method);
             }
         }
@@ -98,23 +101,338 @@ fn extract_class_info(node: &Node, source: &str) -> Option<ClassInfo> {
     })
 }
 
-/// Find all error nodes in the tree
+/// Collect a function's parameter names, paired with their type annotation
+/// (where the grammar exposes one as a `type` field) as `"name: Type"`.
+fn extract_parameters(node: &Node, source: &str) -> Vec<String> {
+    let Some(params_node) = node
+        .child_by_field_name("parameters")
+        .or_else(|| node.child_by_field_name("formal_parameters"))
+    else {
+        return Vec::new();
+    };
+
+    let mut parameters = Vec::new();
+    let mut cursor = params_node.walk();
+    for param in params_node.children(&mut cursor) {
+        if !param.is_named() {
+            continue;
+        }
+
+        let type_text = param
+            .child_by_field_name("type")
+            .and_then(|t| t.utf8_text(source.as_bytes()).ok())
+            .map(|t| t.trim_start_matches(':').trim());
+
+        let mut name_cursor = param.walk();
+        let mut name_nodes: Vec<Node> =
+            param.children_by_field_name("name", &mut name_cursor).collect();
+        if name_nodes.is_empty() {
+            let pattern = param.child_by_field_name("pattern");
+            name_nodes.push(pattern.or_else(|| type_text.and(param.named_child(0))).unwrap_or(param));
+        }
+
+        for name_node in name_nodes {
+            let Ok(name) = name_node.utf8_text(source.as_bytes()) else {
+                continue;
+            };
+            parameters.push(match type_text {
+                Some(type_text) => format!("{}: {}", name, type_text),
+                None => name.to_string(),
+            });
+        }
+    }
+
+    parameters
+}
+
+/// Read a function's return type annotation, if the grammar exposes one
+fn extract_return_type(node: &Node, source: &str) -> Option<String> {
+    let type_node = node
+        .child_by_field_name("return_type")
+        .or_else(|| node.child_by_field_name("type"))
+        .or_else(|| node.child_by_field_name("result"))?;
+    let text = type_node.utf8_text(source.as_bytes()).ok()?;
+    Some(text.trim_start_matches("->").trim().to_string())
+}
+
+/// Detect whether a function/class declaration is exported, using each
+/// language's own convention: a `pub`/`public` modifier for Rust and Java, an
+/// enclosing `export_statement` for JS/TS, and a capitalized identifier for
+/// Go. Python and other languages have no such convention, so it's always
+/// `false` there.
+fn is_exported(node: &Node, source: &str, name: &str, language: &str) -> bool {
+    match language {
+        "go" => name.chars().next().map(|c| c.is_uppercase()).unwrap_or(false),
+        "rust" => has_child_kind(node, "visibility_modifier"),
+        "java" => has_modifier(node, source, "public"),
+        "typescript" | "javascript" => node
+            .parent()
+            .map(|p| p.kind() == "export_statement")
+            .unwrap_or(false),
+        _ => false,
+    }
+}
+
+fn has_child_kind(node: &Node, kind: &str) -> bool {
+    let mut cursor = node.walk();
+    let found = node.children(&mut cursor).any(|c| c.kind() == kind);
+    found
+}
+
+fn has_modifier(node: &Node, source: &str, modifier: &str) -> bool {
+    let mut node_cursor = node.walk();
+    let Some(modifiers) = node.children(&mut node_cursor).find(|c| c.kind() == "modifiers") else {
+        return false;
+    };
+    let mut cursor = modifiers.walk();
+    let found = modifiers.children(&mut cursor).any(|c| {
+        c.utf8_text(source.as_bytes())
+            .map(|text| text == modifier)
+            .unwrap_or(false)
+    });
+    found
+}
+
+/// Extract the module-level import/use declarations from the AST, covering
+/// JS/TS `import_statement`, Python `import_statement`/`import_from_statement`,
+/// Rust `use_declaration`, and Go `import_spec`.
+pub fn extract_imports(root: &Node, source: &str) -> Vec<ImportInfo> {
+    let mut imports = Vec::new();
+
+    let mut cursor = root.walk();
+    for child in root.children(&mut cursor) {
+        match child.kind() {
+            "import_statement" | "import_from_statement" => {
+                if let Some(import) = extract_js_python_import(&child, source) {
+                    imports.push(import);
+                }
+            }
+            "use_declaration" => {
+                imports.extend(extract_rust_use(&child, source));
+            }
+            "import_spec" => {
+                if let Some(import) = extract_go_import_spec(&child, source) {
+                    imports.push(import);
+                }
+            }
+            _ => imports.extend(extract_imports(&child, source)),
+        }
+    }
+
+    imports
+}
+
+/// Handle both JS/TS `import ... from "module"` and Python
+/// `import a, b` / `from module import a, b`.
+fn extract_js_python_import(node: &Node, source: &str) -> Option<ImportInfo> {
+    let module_node = node
+        .child_by_field_name("source")
+        .or_else(|| node.child_by_field_name("module_name"));
+    let module = module_node
+        .and_then(|n| n.utf8_text(source.as_bytes()).ok())
+        .map(|s| s.trim_matches(|c| c == '"' || c == '\'').to_string())
+        .unwrap_or_default();
+    let module_range = module_node.map(|n| n.byte_range());
+
+    let mut names = Vec::new();
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        if module_range.as_ref() == Some(&child.byte_range()) {
+            continue;
+        }
+        collect_import_names(&child, source, &mut names);
+    }
+
+    let is_type_only = node.children(&mut node.walk()).any(|c| c.kind() == "type");
+
+    Some(ImportInfo {
+        source: module,
+        imported_names: names,
+        is_type_only,
+        location: location_of(node),
+    })
+}
+
+/// Collect the plain names bound by an import clause, skipping string
+/// literals (the module path) and punctuation.
+fn collect_import_names(node: &Node, source: &str, names: &mut Vec<String>) {
+    match node.kind() {
+        "identifier" | "dotted_name" | "property_identifier" => {
+            if let Ok(text) = node.utf8_text(source.as_bytes()) {
+                names.push(text.to_string());
+            }
+        }
+        "string" => {}
+        _ => {
+            let mut cursor = node.walk();
+            for child in node.children(&mut cursor) {
+                collect_import_names(&child, source, names);
+            }
+        }
+    }
+}
+
+/// Handle Rust `use a::b::{c, d};` / `use a::b::*;` / `use a::b as c;`,
+/// including arbitrarily nested groups like `use std::{fmt::{self, Display},
+/// io};`. Each leaf of the use-tree becomes its own `ImportInfo`, since a
+/// nested group can bind names from different module paths (`std::fmt` and
+/// `std::io` above) that a single `source`/`imported_names` pair can't
+/// represent. Walks the `scoped_use_list`/`use_list` AST nodes rather than
+/// string-splitting `argument`'s source text, which can't tell a top-level
+/// `::` from one nested inside a `{}` group.
+fn extract_rust_use(node: &Node, source: &str) -> Vec<ImportInfo> {
+    let Some(argument) = node.child_by_field_name("argument") else {
+        return Vec::new();
+    };
+
+    let mut imports = Vec::new();
+    collect_rust_use_tree(&argument, source, "", node, &mut imports);
+    imports
+}
+
+/// Recursively flatten a Rust use-tree node into leaf `ImportInfo`s,
+/// `prefix` being the module path accumulated from enclosing
+/// `scoped_use_list`s. `decl_node` is the top-level `use_declaration`, used
+/// for every leaf's source location.
+fn collect_rust_use_tree(
+    node: &Node,
+    source: &str,
+    prefix: &str,
+    decl_node: &Node,
+    imports: &mut Vec<ImportInfo>,
+) {
+    match node.kind() {
+        "scoped_use_list" => {
+            let prefix = match node
+                .child_by_field_name("path")
+                .and_then(|p| p.utf8_text(source.as_bytes()).ok())
+            {
+                Some(path) => join_rust_path(prefix, path),
+                None => prefix.to_string(),
+            };
+            if let Some(list) = node.child_by_field_name("list") {
+                let mut cursor = list.walk();
+                for item in list.named_children(&mut cursor) {
+                    collect_rust_use_tree(&item, source, &prefix, decl_node, imports);
+                }
+            }
+        }
+        "use_list" => {
+            let mut cursor = node.walk();
+            for item in node.named_children(&mut cursor) {
+                collect_rust_use_tree(&item, source, prefix, decl_node, imports);
+            }
+        }
+        "use_as_clause" => {
+            let path = node
+                .child_by_field_name("path")
+                .and_then(|p| p.utf8_text(source.as_bytes()).ok())
+                .unwrap_or_default();
+            let alias = node
+                .child_by_field_name("alias")
+                .and_then(|a| a.utf8_text(source.as_bytes()).ok())
+                .unwrap_or_default();
+            let full = join_rust_path(prefix, path);
+            let name = path.rsplit("::").next().unwrap_or(path);
+            let module = match full.rsplit_once("::") {
+                Some((module, _)) => module.to_string(),
+                None => full,
+            };
+            imports.push(ImportInfo {
+                source: module,
+                imported_names: vec![format!("{} as {}", name, alias)],
+                is_type_only: false,
+                location: location_of(decl_node),
+            });
+        }
+        "use_wildcard" => {
+            let path = node
+                .named_child(0)
+                .and_then(|p| p.utf8_text(source.as_bytes()).ok());
+            let module = match path {
+                Some(path) => join_rust_path(prefix, path),
+                None => prefix.to_string(),
+            };
+            imports.push(ImportInfo {
+                source: module,
+                imported_names: vec!["*".to_string()],
+                is_type_only: false,
+                location: location_of(decl_node),
+            });
+        }
+        "self" => {
+            imports.push(ImportInfo {
+                source: prefix.to_string(),
+                imported_names: vec!["self".to_string()],
+                is_type_only: false,
+                location: location_of(decl_node),
+            });
+        }
+        "identifier" | "scoped_identifier" => {
+            let Ok(path) = node.utf8_text(source.as_bytes()) else {
+                return;
+            };
+            let full = join_rust_path(prefix, path);
+            let name = path.rsplit("::").next().unwrap_or(path).to_string();
+            let module = match full.rsplit_once("::") {
+                Some((module, _)) => module.to_string(),
+                None => full,
+            };
+            imports.push(ImportInfo {
+                source: module,
+                imported_names: vec![name],
+                is_type_only: false,
+                location: location_of(decl_node),
+            });
+        }
+        _ => {}
+    }
+}
+
+/// Join an enclosing use-tree prefix (may be empty, at the declaration's
+/// top level) with a path segment.
+fn join_rust_path(prefix: &str, segment: &str) -> String {
+    if prefix.is_empty() {
+        segment.to_string()
+    } else {
+        format!("{}::{}", prefix, segment)
+    }
+}
+
+/// Handle a single Go `import_spec` (one line of an `import (...)` block)
+fn extract_go_import_spec(node: &Node, source: &str) -> Option<ImportInfo> {
+    let path_node = node.child_by_field_name("path")?;
+    let path = path_node
+        .utf8_text(source.as_bytes())
+        .ok()?
+        .trim_matches('"')
+        .to_string();
+    let alias = node
+        .child_by_field_name("name")
+        .and_then(|n| n.utf8_text(source.as_bytes()).ok())
+        .map(|s| s.to_string());
+
+    Some(ImportInfo {
+        source: path,
+        imported_names: alias.into_iter().collect(),
+        is_type_only: false,
+        location: location_of(node),
+    })
+}
+
+/// Find all error nodes in the tree, producing one diagnostic per malformed
+/// region rather than descending into a region's own `ERROR`/`MISSING`
+/// descendants (which would otherwise report the same breakage many times).
 pub fn find_error_nodes(node: &Node, source: &str) -> Vec<ErrorNode> {
-    let mut errors = Vec::new();
+    if node.is_missing() {
+        return vec![missing_diagnostic(node, source)];
+    }
 
-    if node.is_error() || node.is_missing() {
-        errors.push(ErrorNode {
-            message: "Syntax error".to_string(),
-            location: SourceLocation {
-                start_row: node.start_position().row,
-                start_column: node.start_position().column,
-                end_row: node.end_position().row,
-                end_column: node.end_position().column,
-            },
-            text: source[node.byte_range()].to_string(),
-        });
+    if node.is_error() {
+        return vec![error_diagnostic(node, source)];
     }
 
+    let mut errors = Vec::new();
     let mut cursor = node.walk();
     for child in node.children(&mut cursor) {
         errors.extend(find_error_nodes(&child, source));
@@ -122,3 +440,162 @@ pub fn find_error_nodes(node: &Node, source: &str) -> Vec<ErrorNode> {
 
     errors
 }
+
+/// A `MISSING` node's own kind names the grammar production tree-sitter
+/// expected but never found (e.g. `;`), so it's reported as an error.
+fn missing_diagnostic(node: &Node, source: &str) -> ErrorNode {
+    let (source_line, caret_column) = source_line_and_caret(node, source);
+    ErrorNode {
+        message: format!("expected {}", node.kind()),
+        severity: Severity::Error,
+        location: location_of(node),
+        text: source[node.byte_range()].to_string(),
+        source_line,
+        caret_column,
+    }
+}
+
+/// An `ERROR` node is tree-sitter's best-effort recovery around an
+/// unexpected construct; this is common mid-edit, so it's reported as a
+/// warning rather than a hard error.
+fn error_diagnostic(node: &Node, source: &str) -> ErrorNode {
+    let (source_line, caret_column) = source_line_and_caret(node, source);
+    ErrorNode {
+        message: describe_error_node(node, source),
+        severity: Severity::Warning,
+        location: location_of(node),
+        text: source[node.byte_range()].to_string(),
+        source_line,
+        caret_column,
+    }
+}
+
+/// Describe an `ERROR` node using the kinds of the sibling nodes around it,
+/// since the node's own kind is always just `"ERROR"`.
+fn describe_error_node(node: &Node, source: &str) -> String {
+    let snippet = node
+        .utf8_text(source.as_bytes())
+        .unwrap_or("")
+        .trim();
+    let prev = node.prev_sibling().map(|n| n.kind());
+    let next = node.next_sibling().map(|n| n.kind());
+
+    match (prev, next) {
+        (Some(p), Some(n)) => format!("unexpected `{}` between {} and {}", snippet, p, n),
+        (Some(p), None) => format!("unexpected `{}` after {}", snippet, p),
+        (None, Some(n)) => format!("unexpected `{}` before {}", snippet, n),
+        (None, None) => format!("unexpected `{}`", snippet),
+    }
+}
+
+fn location_of(node: &Node) -> SourceLocation {
+    SourceLocation {
+        start_row: node.start_position().row,
+        start_column: node.start_position().column,
+        end_row: node.end_position().row,
+        end_column: node.end_position().column,
+    }
+}
+
+/// The full source line the node starts on, plus the column a caret should
+/// point at when rendering the diagnostic.
+fn source_line_and_caret(node: &Node, source: &str) -> (String, usize) {
+    let position = node.start_position();
+    let line = source.lines().nth(position.row).unwrap_or("").to_string();
+    (line, position.column)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tree_sitter::Parser;
+
+    fn parse(language: &str, source: &str) -> tree_sitter::Tree {
+        let mut parser = Parser::new();
+        let lang = match language {
+            "rust" => tree_sitter_rust::language(),
+            "java" => tree_sitter_java::language(),
+            other => panic!("unsupported test language: {other}"),
+        };
+        parser.set_language(lang).unwrap();
+        parser.parse(source, None).unwrap()
+    }
+
+    #[test]
+    fn is_exported_detects_pub_rust_function() {
+        let source = "pub fn visible() {}\nfn hidden() {}\n";
+        let tree = parse("rust", source);
+        let functions = extract_functions(&tree.root_node(), source, "rust");
+
+        let visible = functions.iter().find(|f| f.name == "visible").unwrap();
+        let hidden = functions.iter().find(|f| f.name == "hidden").unwrap();
+        assert!(visible.is_exported);
+        assert!(!hidden.is_exported);
+    }
+
+    #[test]
+    fn is_exported_detects_public_java_method() {
+        let source = "class Foo {\n  public void visible() {}\n  void hidden() {}\n}\n";
+        let tree = parse("java", source);
+        let classes = extract_classes(&tree.root_node(), source, "java");
+        let methods = &classes[0].methods;
+
+        let visible = methods.iter().find(|f| f.name == "visible").unwrap();
+        let hidden = methods.iter().find(|f| f.name == "hidden").unwrap();
+        assert!(visible.is_exported);
+        assert!(!hidden.is_exported);
+    }
+
+    #[test]
+    fn extract_rust_use_flattens_nested_groups() {
+        let source = "use std::{fmt::{self, Display}, io};\n";
+        let tree = parse("rust", source);
+        let imports = extract_imports(&tree.root_node(), source);
+
+        assert_eq!(imports.len(), 3);
+        assert!(imports
+            .iter()
+            .any(|i| i.source == "std::fmt" && i.imported_names == vec!["self".to_string()]));
+        assert!(imports
+            .iter()
+            .any(|i| i.source == "std::fmt" && i.imported_names == vec!["Display".to_string()]));
+        assert!(imports
+            .iter()
+            .any(|i| i.source == "std" && i.imported_names == vec!["io".to_string()]));
+    }
+
+    #[test]
+    fn find_error_nodes_reports_missing_token_once() {
+        let source = "fn foo() {\n    let x = 1\n    let y = 2;\n}\n";
+        let tree = parse("rust", source);
+        let errors = find_error_nodes(&tree.root_node(), source);
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].severity, Severity::Error);
+        assert_eq!(errors[0].message, "expected ;");
+    }
+
+    #[test]
+    fn find_error_nodes_does_not_recurse_into_error_subtree() {
+        let source = "fn foo() {\n    @@@\n}\n";
+        let tree = parse("rust", source);
+        let errors = find_error_nodes(&tree.root_node(), source);
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].severity, Severity::Warning);
+        assert_eq!(errors[0].message, "unexpected `@@@` between { and }");
+    }
+
+    #[test]
+    fn extract_rust_use_handles_wildcard_and_alias() {
+        let source = "use std::io::*;\nuse std::collections::HashMap as Map;\n";
+        let tree = parse("rust", source);
+        let imports = extract_imports(&tree.root_node(), source);
+
+        assert!(imports
+            .iter()
+            .any(|i| i.source == "std::io" && i.imported_names == vec!["*".to_string()]));
+        assert!(imports.iter().any(|i| i.source == "std::collections"
+            && i.imported_names == vec!["HashMap as Map".to_string()]));
+    }
+}
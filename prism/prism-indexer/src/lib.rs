@@ -4,6 +4,8 @@ mod types;
 mod chunker;
 mod extractor;
 
+use serde::Deserialize;
+use std::collections::HashSet;
 use wasm_bindgen::prelude::*;
 
 // Re-export the main parser
@@ -27,10 +29,139 @@ pub fn create_parser(language: &str) -> Result<PrismParser, JsValue> {
 #[wasm_bindgen]
 pub fn parse_code(code: &str, language: &str) -> Result<JsValue, JsValue> {
     let mut parser = PrismParser::new(language)?;
-    let result = parser.parse(code)?;
+    let result = parser.parse_sync(code)?;
     serde_wasm_bindgen::to_value(&result).map_err(|e| JsValue::from_str(&e.to_string()))
 }
 
+/// A single file handed to `parse_project`
+#[derive(Debug, Deserialize)]
+struct ProjectFile {
+    path: String,
+    language: String,
+    code: String,
+}
+
+/// Parse an entire project in one call.
+///
+/// `files` is a JS array of `{path, language, code}` objects. Each file is
+/// parsed independently, its chunk IDs are namespaced by file path, and
+/// every chunk's `dependencies` are resolved against the other files' paths
+/// and exported symbols so cross-file references point at a real file
+/// instead of a raw module string. This is the batch entry point a code-RAG
+/// indexer uses to embed a whole project instead of one file at a time.
+#[wasm_bindgen]
+pub fn parse_project(files: JsValue) -> Result<JsValue, JsValue> {
+    let files: Vec<ProjectFile> = serde_wasm_bindgen::from_value(files)
+        .map_err(|e| JsValue::from_str(&format!("Invalid files: {}", e)))?;
+
+    let mut chunks = Vec::new();
+    let mut exported_symbols = Vec::new();
+    for file in &files {
+        let mut parser = PrismParser::new(&file.language)?;
+        let result = parser.parse_sync(&file.code)?;
+
+        exported_symbols.push(file_exported_symbols(&result));
+
+        for mut chunk in result.chunks {
+            chunk.id = format!("{}::{}", file.path, chunk.id);
+            chunks.push(chunk);
+        }
+    }
+
+    let paths: Vec<&str> = files.iter().map(|f| f.path.as_str()).collect();
+    for chunk in &mut chunks {
+        let resolved = chunk
+            .dependencies
+            .iter()
+            .map(|dep| {
+                let imported_names = chunk
+                    .imports
+                    .iter()
+                    .find(|import| &import.source == dep)
+                    .map(|import| import.imported_names.as_slice())
+                    .unwrap_or(&[]);
+                resolve_cross_file_dependency(dep, &paths, imported_names, &exported_symbols)
+                    .unwrap_or_else(|| dep.clone())
+            })
+            .collect();
+        chunk.dependencies = resolved;
+    }
+
+    serde_wasm_bindgen::to_value(&chunks).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// The names a file exports: every function in `result.functions` with
+/// `is_exported` set (class methods and nested functions included), plus
+/// every class in `result.classes` (`ClassInfo` has no export flag the way
+/// `FunctionInfo::is_exported` does, so every class is treated as part of
+/// the surface).
+fn file_exported_symbols(result: &ParseResult) -> HashSet<String> {
+    result
+        .functions
+        .iter()
+        .filter(|f| f.is_exported)
+        .map(|f| f.name.clone())
+        .chain(result.classes.iter().map(|c| c.name.clone()))
+        .collect()
+}
+
+/// Resolve a raw import source (e.g. `./utils` or `pkg/models`) to another
+/// parsed file's path by matching path suffixes on segment boundaries (so
+/// `./config` matches `src/config.ts` but not `src/bigconfig.ts`), then
+/// confirming that at least one of the names actually imported from it is
+/// genuinely exported by that candidate file. A wildcard/`self` import, or
+/// one that binds no names (e.g. a Go import without an alias), has no
+/// specific name to check, so the first path match is accepted as-is; otherwise a
+/// path match whose candidate doesn't export any of the imported names is
+/// rejected rather than silently accepted, since same-named-but-unrelated
+/// modules (e.g. two different `utils.ts`) are a real false-positive source.
+fn resolve_cross_file_dependency(
+    dep: &str,
+    paths: &[&str],
+    imported_names: &[String],
+    exported_symbols: &[HashSet<String>],
+) -> Option<String> {
+    let normalized = dep.trim_start_matches("./").trim_start_matches('/');
+    let candidates: Vec<usize> = paths
+        .iter()
+        .enumerate()
+        .filter(|(_, path)| {
+            let stem = path.trim_end_matches(|c: char| c != '.').trim_end_matches('.');
+            path_suffix_matches(path, normalized) || path_suffix_matches(stem, normalized)
+        })
+        .map(|(i, _)| i)
+        .collect();
+
+    if imported_names.is_empty() || imported_names.iter().any(|n| n == "*" || n == "self") {
+        return candidates.first().map(|&i| paths[i].to_string());
+    }
+
+    candidates
+        .into_iter()
+        .find(|&i| {
+            imported_names
+                .iter()
+                .any(|name| exported_symbols[i].contains(bare_imported_name(name)))
+        })
+        .map(|i| paths[i].to_string())
+}
+
+/// Strip a Rust `name as alias` binding down to the name the source module
+/// actually exports, which is what should be checked against its exports.
+fn bare_imported_name(name: &str) -> &str {
+    name.split(" as ").next().unwrap_or(name)
+}
+
+/// Whether `normalized` matches the end of `path` on a `/` segment boundary,
+/// i.e. `path` equals `normalized` or ends with `/` + `normalized`.
+fn path_suffix_matches(path: &str, normalized: &str) -> bool {
+    path == normalized
+        || path
+            .strip_suffix(normalized)
+            .map(|prefix| prefix.ends_with('/'))
+            .unwrap_or(false)
+}
+
 /// Get supported languages
 #[wasm_bindgen]
 pub fn get_supported_languages() -> JsValue {
@@ -50,3 +181,69 @@ pub fn get_supported_languages() -> JsValue {
 pub fn get_version() -> String {
     env!("CARGO_PKG_VERSION").to_string()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exports(names: &[&str]) -> HashSet<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn resolve_cross_file_dependency_requires_segment_boundary() {
+        let paths = ["src/bigconfig.ts", "src/other.ts"];
+        let exported = [exports(&["helper"]), exports(&[])];
+        assert_eq!(
+            resolve_cross_file_dependency("./config", &paths, &["helper".to_string()], &exported),
+            None
+        );
+    }
+
+    #[test]
+    fn resolve_cross_file_dependency_matches_on_segment_boundary() {
+        let paths = ["src/bigconfig.ts", "src/config.ts"];
+        let exported = [exports(&[]), exports(&["helper"])];
+        assert_eq!(
+            resolve_cross_file_dependency("./config", &paths, &["helper".to_string()], &exported),
+            Some("src/config.ts".to_string())
+        );
+    }
+
+    #[test]
+    fn resolve_cross_file_dependency_matches_nested_path() {
+        let paths = ["src/utils/config.ts"];
+        let exported = [exports(&["helper"])];
+        assert_eq!(
+            resolve_cross_file_dependency(
+                "utils/config",
+                &paths,
+                &["helper".to_string()],
+                &exported
+            ),
+            Some("src/utils/config.ts".to_string())
+        );
+    }
+
+    #[test]
+    fn resolve_cross_file_dependency_rejects_path_match_missing_the_export() {
+        // `from ./utils import helper` shouldn't resolve to a same-named
+        // `utils.ts` that never actually exports `helper`.
+        let paths = ["src/utils.ts"];
+        let exported = [exports(&["unrelated"])];
+        assert_eq!(
+            resolve_cross_file_dependency("./utils", &paths, &["helper".to_string()], &exported),
+            None
+        );
+    }
+
+    #[test]
+    fn resolve_cross_file_dependency_accepts_wildcard_import_on_path_match_alone() {
+        let paths = ["src/utils.ts"];
+        let exported = [exports(&[])];
+        assert_eq!(
+            resolve_cross_file_dependency("./utils", &paths, &["*".to_string()], &exported),
+            Some("src/utils.ts".to_string())
+        );
+    }
+}
@@ -1,6 +1,8 @@
+use crate::chunker::TokenEstimator;
 use crate::error::{PrismError, Result};
-use crate::types::{ParseResult, CodeChunk};
-use tree_sitter::Parser;
+use crate::types::{ChangedRange, IncrementalParseResult, ParseResult};
+use serde::Deserialize;
+use tree_sitter::{InputEdit, Parser, Point, Tree};
 use wasm_bindgen::prelude::*;
 
 /// Main parser struct
@@ -8,6 +10,45 @@ use wasm_bindgen::prelude::*;
 pub struct PrismParser {
     parser: Parser,
     language_name: String,
+    tree: Option<Tree>,
+    token_estimator: TokenEstimator,
+}
+
+/// A row/column position, as sent across the WASM boundary
+#[derive(Debug, Deserialize)]
+struct PositionInput {
+    row: usize,
+    column: usize,
+}
+
+impl From<PositionInput> for Point {
+    fn from(position: PositionInput) -> Self {
+        Point::new(position.row, position.column)
+    }
+}
+
+/// A single edit to apply to the previously parsed tree before reparsing
+#[derive(Debug, Deserialize)]
+struct EditInput {
+    start_byte: usize,
+    old_end_byte: usize,
+    new_end_byte: usize,
+    start_position: PositionInput,
+    old_end_position: PositionInput,
+    new_end_position: PositionInput,
+}
+
+impl From<EditInput> for InputEdit {
+    fn from(edit: EditInput) -> Self {
+        InputEdit {
+            start_byte: edit.start_byte,
+            old_end_byte: edit.old_end_byte,
+            new_end_byte: edit.new_end_byte,
+            start_position: edit.start_position.into(),
+            old_end_position: edit.old_end_position.into(),
+            new_end_position: edit.new_end_position.into(),
+        }
+    }
 }
 
 #[wasm_bindgen]
@@ -19,62 +60,153 @@ impl PrismParser {
 
         let language_obj = match language {
             "typescript" => tree_sitter_typescript::language_typescript(),
-            "javascript" => tree_sitter_javascript::language_javascript(),
-            "python" => tree_sitter_python::language_python(),
-            "rust" => tree_sitter_rust::language_rust(),
-            "go" => tree_sitter_go::language_go(),
-            "java" => tree_sitter_java::language_java(),
+            "javascript" => tree_sitter_javascript::language(),
+            "python" => tree_sitter_python::language(),
+            "rust" => tree_sitter_rust::language(),
+            "go" => tree_sitter_go::language(),
+            "java" => tree_sitter_java::language(),
             _ => return Err(PrismError::UnsupportedLanguage(language.to_string())),
         };
 
         parser
-            .set_language(&language_obj)
+            .set_language(language_obj)
             .map_err(|e| PrismError::ParseError(format!("Failed to set language: {:?}", e)))?;
 
         Ok(PrismParser {
             parser,
             language_name: language.to_string(),
+            tree: None,
+            token_estimator: TokenEstimator::default(),
         })
     }
 
-    /// Parse code and return structured result
+    /// Select the token-estimation strategy used by future `parse`/
+    /// `parse_incremental` calls: `"heuristic"` (fast, default) or
+    /// `"accurate"` (subword-aware, enforces `MAX_CHUNK_SIZE` against the
+    /// more precise count).
     #[wasm_bindgen]
-    pub fn parse(&mut self, code: &str) -> Result<ParseResult> {
+    pub fn set_token_estimator(&mut self, mode: &str) -> Result<()> {
+        self.token_estimator = match mode {
+            "heuristic" => TokenEstimator::Heuristic,
+            "accurate" => TokenEstimator::Accurate,
+            other => return Err(PrismError::ParseError(format!("Unknown token estimator: {}", other))),
+        };
+        Ok(())
+    }
+
+    /// Parse code and return the result as a plain JS object
+    #[wasm_bindgen]
+    pub fn parse(&mut self, code: &str) -> Result<JsValue> {
+        let result = self.parse_sync(code)?;
+        serde_wasm_bindgen::to_value(&result)
+            .map_err(|e| PrismError::ParseError(e.to_string()))
+    }
+
+    /// Reparse `new_code` by reusing the tree from the previous `parse`/
+    /// `parse_incremental` call instead of starting from scratch.
+    ///
+    /// `edits` is a JS array of edit descriptors (`start_byte`,
+    /// `old_end_byte`, `new_end_byte`, and the corresponding row/column
+    /// positions), applied to the cached tree in order before reparsing.
+    /// Returns the new `ParseResult` together with the byte ranges
+    /// tree-sitter reports as changed, so a host editor only needs to
+    /// re-extract chunks that touch those ranges.
+    #[wasm_bindgen]
+    pub fn parse_incremental(&mut self, new_code: &str, edits: JsValue) -> Result<JsValue> {
+        let edits: Vec<EditInput> = serde_wasm_bindgen::from_value(edits)
+            .map_err(|e| PrismError::ParseError(format!("Invalid edits: {}", e)))?;
+
+        let result = self.parse_incremental_sync(new_code, edits)?;
+        serde_wasm_bindgen::to_value(&result)
+            .map_err(|e| PrismError::ParseError(e.to_string()))
+    }
+
+    /// Clean up resources
+    #[wasm_bindgen]
+    pub fn free(&mut self) {
+        // Tree-sitter Parser doesn't have explicit cleanup
+        // This is a placeholder for future cleanup needs
+    }
+}
+
+impl PrismParser {
+    /// Parse code and return the structured result, without the wasm
+    /// boundary conversion, so callers that stay on the Rust side (the
+    /// wasm-exposed `parse` method, `parse_code`, `parse_project`) can work
+    /// with `ParseResult` directly instead of a `JsValue`.
+    pub(crate) fn parse_sync(&mut self, code: &str) -> Result<ParseResult> {
         let tree = self
             .parser
             .parse(code, None)
             .ok_or_else(|| PrismError::ParseError("Failed to parse code".to_string()))?;
 
+        let result = self.build_parse_result(&tree, code);
+        self.tree = Some(tree);
+        Ok(result)
+    }
+
+    /// Reparse `new_code` by reusing the tree from the previous `parse_sync`/
+    /// `parse_incremental_sync` call instead of starting from scratch; the
+    /// `JsValue` conversion of `edits` is already done by the caller. See
+    /// `parse_sync` for why this stays on the Rust side of the wasm boundary.
+    fn parse_incremental_sync(
+        &mut self,
+        new_code: &str,
+        edits: Vec<EditInput>,
+    ) -> Result<IncrementalParseResult> {
+        let mut old_tree = self.tree.take().ok_or_else(|| {
+            PrismError::ParseError("No previous parse to reuse; call parse() first".to_string())
+        })?;
+
+        for edit in edits {
+            old_tree.edit(&edit.into());
+        }
+
+        let new_tree = self
+            .parser
+            .parse(new_code, Some(&old_tree))
+            .ok_or_else(|| PrismError::ParseError("Failed to parse code".to_string()))?;
+
+        let changed_ranges = old_tree
+            .changed_ranges(&new_tree)
+            .map(|range| ChangedRange {
+                start_byte: range.start_byte,
+                end_byte: range.end_byte,
+            })
+            .collect();
+
+        let result = self.build_parse_result(&new_tree, new_code);
+        self.tree = Some(new_tree);
+
+        Ok(IncrementalParseResult {
+            result,
+            changed_ranges,
+        })
+    }
+
+    /// Build a `ParseResult` from a parsed tree, shared by `parse_sync` and
+    /// `parse_incremental_sync`
+    fn build_parse_result(&self, tree: &Tree, code: &str) -> ParseResult {
         let root = tree.root_node();
         let has_errors = root.has_error();
 
-        // Extract code chunks
-        let chunks = crate::chunker::chunk_code(&root, code, &self.language_name);
+        let chunks =
+            crate::chunker::chunk_code(&root, code, &self.language_name, self.token_estimator);
+        let functions = crate::extractor::extract_functions(&root, code, &self.language_name);
+        let classes = crate::extractor::extract_classes(&root, code, &self.language_name);
 
-        // Extract functions and classes
-        let functions = crate::extractor::extract_functions(&root, code);
-        let classes = crate::extractor::extract_classes(&root, code);
-
-        // Find error nodes if any
         let error_nodes = if has_errors {
             crate::extractor::find_error_nodes(&root, code)
         } else {
             Vec::new()
         };
 
-        Ok(ParseResult {
+        ParseResult {
             has_errors,
             error_nodes,
             chunks,
             functions,
             classes,
-        })
-    }
-
-    /// Clean up resources
-    #[wasm_bindgen]
-    pub fn free(&mut self) {
-        // Tree-sitter Parser doesn't have explicit cleanup
-        // This is a placeholder for future cleanup needs
+        }
     }
 }
@@ -55,6 +55,11 @@ pub struct CodeChunk {
     pub classes: Vec<ClassInfo>,
     pub imports: Vec<ImportInfo>,
     pub dependencies: Vec<String>,
+    /// Flattened names of the functions/classes contained in this chunk
+    pub symbols: Vec<String>,
+    /// Blake3 hash of the chunk's normalized text, so an embedding cache can
+    /// skip re-embedding chunks whose content hasn't changed
+    pub content_hash: String,
 }
 
 /// Result of parsing code
@@ -67,10 +72,36 @@ pub struct ParseResult {
     pub classes: Vec<ClassInfo>,
 }
 
+/// How serious a syntax diagnostic is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
 /// Error node information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ErrorNode {
     pub message: String,
+    pub severity: Severity,
     pub location: SourceLocation,
     pub text: String,
+    pub source_line: String,
+    pub caret_column: usize,
+}
+
+/// A byte range tree-sitter reports as changed by an incremental reparse
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangedRange {
+    pub start_byte: usize,
+    pub end_byte: usize,
+}
+
+/// Result of an incremental reparse: the usual `ParseResult` plus the byte
+/// ranges that actually changed, so a host editor can re-extract only the
+/// chunks that touch them instead of the whole file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IncrementalParseResult {
+    pub result: ParseResult,
+    pub changed_ranges: Vec<ChangedRange>,
 }